@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use crate::types::non_empty::NonEmptyVec;
 
@@ -33,10 +35,92 @@ pub struct Task {
     pub name: Option<String>,
 }
 
+/// Whether a gateway is merging branches together or fanning one out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GatewayDirection {
+    Split,
+    Join,
+}
+
+/// Routes tokens along exactly one of its outgoing flows. As a split, the
+/// flow is chosen by `weights` (see `execution::gateway`); as a join, any
+/// single incoming flow carrying a token is enough to fire.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Gateway {
+pub struct ExclusiveGateway {
     pub id: String,
     pub name: Option<String>,
+    pub direction: GatewayDirection,
+    pub incoming: Vec<String>,
+    pub outgoing: Vec<String>,
+    /// Relative weight of each outgoing flow id, used for weighted random
+    /// selection when this gateway splits. Flows with no entry default to
+    /// a weight of 1.
+    pub weights: HashMap<String, u32>,
+}
+
+/// Fans tokens out to every outgoing flow as a split, or waits for a token
+/// on every incoming flow before proceeding as a join.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParallelGateway {
+    pub id: String,
+    pub name: Option<String>,
+    pub direction: GatewayDirection,
+    pub incoming: Vec<String>,
+    pub outgoing: Vec<String>,
+}
+
+/// Structurally identical to `ParallelGateway`: this crate has no
+/// condition expressions to select a subset of outgoing flows, so an
+/// inclusive split/join currently behaves like an all-or-nothing
+/// parallel one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusiveGateway {
+    pub id: String,
+    pub name: Option<String>,
+    pub direction: GatewayDirection,
+    pub incoming: Vec<String>,
+    pub outgoing: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Gateway {
+    Exclusive(ExclusiveGateway),
+    Parallel(ParallelGateway),
+    Inclusive(InclusiveGateway),
+}
+
+impl Gateway {
+    pub fn id(&self) -> &str {
+        match self {
+            Gateway::Exclusive(g) => &g.id,
+            Gateway::Parallel(g) => &g.id,
+            Gateway::Inclusive(g) => &g.id,
+        }
+    }
+
+    pub fn direction(&self) -> GatewayDirection {
+        match self {
+            Gateway::Exclusive(g) => g.direction,
+            Gateway::Parallel(g) => g.direction,
+            Gateway::Inclusive(g) => g.direction,
+        }
+    }
+
+    pub fn incoming(&self) -> &[String] {
+        match self {
+            Gateway::Exclusive(g) => &g.incoming,
+            Gateway::Parallel(g) => &g.incoming,
+            Gateway::Inclusive(g) => &g.incoming,
+        }
+    }
+
+    pub fn outgoing(&self) -> &[String] {
+        match self {
+            Gateway::Exclusive(g) => &g.outgoing,
+            Gateway::Parallel(g) => &g.outgoing,
+            Gateway::Inclusive(g) => &g.outgoing,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -0,0 +1,175 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::core::definitions::Process;
+
+/// A sequence flow's `source_ref` or `target_ref` does not name any start
+/// event, task, gateway, or end event in the process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexError {
+    DanglingSourceRef { flow_id: String, source_ref: String },
+    DanglingTargetRef { flow_id: String, target_ref: String },
+}
+
+/// Precomputed source/target adjacency for a `Process`'s sequence flows.
+///
+/// Executing a process by scanning every flow node against every sequence
+/// flow is O(nodes x flows) per step. `ProcessIndex` is built once and
+/// answers "which flows leave/enter this node" and "which flow has this
+/// id" in O(1), so execution only has to look at the active frontier.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProcessIndex {
+    /// Node id -> indices into `Process::sequence_flows` leaving it.
+    outgoing: HashMap<String, Vec<usize>>,
+    /// Node id -> indices into `Process::sequence_flows` entering it.
+    incoming: HashMap<String, Vec<usize>>,
+    /// Sequence flow id -> its index into `Process::sequence_flows`.
+    flow_index: HashMap<String, usize>,
+}
+
+impl ProcessIndex {
+    /// Builds the adjacency indices for `process`, rejecting any sequence
+    /// flow whose `source_ref` or `target_ref` does not name a node that
+    /// actually exists in the process.
+    pub fn build(process: &Process) -> Result<Self, IndexError> {
+        let node_ids: HashSet<&str> = process
+            .start_events
+            .iter()
+            .map(|e| e.id.as_str())
+            .chain(process.tasks.iter().map(|t| t.id.as_str()))
+            .chain(process.gateways.iter().map(|g| g.id()))
+            .chain(process.end_events.iter().map(|e| e.id.as_str()))
+            .collect();
+
+        let mut index = ProcessIndex::default();
+        for (flow_idx, flow) in process.sequence_flows.iter().enumerate() {
+            if !node_ids.contains(flow.source_ref.as_str()) {
+                return Err(IndexError::DanglingSourceRef {
+                    flow_id: flow.id.clone(),
+                    source_ref: flow.source_ref.clone(),
+                });
+            }
+            if !node_ids.contains(flow.target_ref.as_str()) {
+                return Err(IndexError::DanglingTargetRef {
+                    flow_id: flow.id.clone(),
+                    target_ref: flow.target_ref.clone(),
+                });
+            }
+            index.flow_index.insert(flow.id.clone(), flow_idx);
+            index
+                .outgoing
+                .entry(flow.source_ref.clone())
+                .or_default()
+                .push(flow_idx);
+            index
+                .incoming
+                .entry(flow.target_ref.clone())
+                .or_default()
+                .push(flow_idx);
+        }
+        Ok(index)
+    }
+
+    /// Sequence flow ids leaving `node_id`.
+    pub fn outgoing_flow_ids<'a>(&self, process: &'a Process, node_id: &str) -> Vec<&'a str> {
+        self.outgoing
+            .get(node_id)
+            .into_iter()
+            .flatten()
+            .map(|&idx| process.sequence_flows[idx].id.as_str())
+            .collect()
+    }
+
+    /// Sequence flow ids entering `node_id`.
+    pub fn incoming_flow_ids<'a>(&self, process: &'a Process, node_id: &str) -> Vec<&'a str> {
+        self.incoming
+            .get(node_id)
+            .into_iter()
+            .flatten()
+            .map(|&idx| process.sequence_flows[idx].id.as_str())
+            .collect()
+    }
+
+    /// The node `flow_id` leads into, if it names a known sequence flow.
+    pub fn target_of<'a>(&self, process: &'a Process, flow_id: &str) -> Option<&'a str> {
+        self.flow_index
+            .get(flow_id)
+            .map(|&idx| process.sequence_flows[idx].target_ref.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::definitions::{EndEvent, SequenceFlow, StartEvent, Task};
+    use crate::types::non_empty::NonEmptyVec;
+
+    fn flow(id: &str, source_ref: &str, target_ref: &str) -> SequenceFlow {
+        SequenceFlow {
+            id: id.to_string(),
+            name: None,
+            source_ref: source_ref.to_string(),
+            target_ref: target_ref.to_string(),
+        }
+    }
+
+    /// `Start -> T1 -> End`.
+    fn linear_process() -> Process {
+        Process {
+            id: "linear".to_string(),
+            name: None,
+            start_events: NonEmptyVec::new(StartEvent {
+                id: "s".to_string(),
+                name: None,
+            }),
+            tasks: vec![Task {
+                id: "t1".to_string(),
+                name: None,
+            }],
+            gateways: vec![],
+            end_events: vec![EndEvent {
+                id: "end".to_string(),
+                name: None,
+            }],
+            sequence_flows: vec![flow("f0", "s", "t1"), flow("f1", "t1", "end")],
+        }
+    }
+
+    #[test]
+    fn build_indexes_outgoing_and_incoming_adjacency() {
+        let process = linear_process();
+        let index = ProcessIndex::build(&process).unwrap();
+        assert_eq!(index.outgoing_flow_ids(&process, "s"), vec!["f0"]);
+        assert_eq!(index.incoming_flow_ids(&process, "t1"), vec!["f0"]);
+        assert_eq!(index.outgoing_flow_ids(&process, "t1"), vec!["f1"]);
+        assert_eq!(index.target_of(&process, "f0"), Some("t1"));
+        assert!(index.outgoing_flow_ids(&process, "end").is_empty());
+    }
+
+    #[test]
+    fn rejects_dangling_source_ref() {
+        let mut process = linear_process();
+        process.sequence_flows.push(flow("f2", "nonexistent", "end"));
+        let err = ProcessIndex::build(&process).unwrap_err();
+        assert_eq!(
+            err,
+            IndexError::DanglingSourceRef {
+                flow_id: "f2".to_string(),
+                source_ref: "nonexistent".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_dangling_target_ref() {
+        let mut process = linear_process();
+        process.sequence_flows.push(flow("f2", "t1", "nonexistent"));
+        let err = ProcessIndex::build(&process).unwrap_err();
+        assert_eq!(
+            err,
+            IndexError::DanglingTargetRef {
+                flow_id: "f2".to_string(),
+                target_ref: "nonexistent".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,220 @@
+//! Stochastic path selection for exclusive gateways and Monte-Carlo style
+//! simulation runs built on top of it.
+//!
+//! `explore` (in `execution::snapshot`) branches over every outgoing flow
+//! of an exclusive split so the reachability graph covers every path a
+//! process could take. `simulate_run` instead commits to a single path per
+//! run, picking among an exclusive split's outgoing flows with a weighted
+//! random draw, so that running it many times approximates how often each
+//! path is taken in practice.
+
+use std::collections::HashMap;
+
+use crate::core::definitions::{Gateway, GatewayDirection, Process};
+use crate::core::index::{IndexError, ProcessIndex};
+use crate::execution::snapshot::{consume_enabling_tokens, enabled_nodes, find_gateway, initial_snapshot, Snapshot};
+
+/// A seedable pseudo-random generator, so a simulation run can be replayed
+/// exactly by reusing its seed. Uses the SplitMix64 algorithm: small,
+/// dependency-free, and sufficient for weighted path selection.
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    /// Creates a generator that will always produce the same sequence of
+    /// draws for a given `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Next pseudo-random `u64`, advancing the generator's state.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Next pseudo-random value uniformly distributed in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+/// Picks one outgoing flow id from `weights` (flow id, weight) pairs: draws
+/// a uniform value `u` in `[0, sum(weights))`, walks the cumulative sums,
+/// and returns the first flow whose running total exceeds `u`.
+///
+/// Returns `None` if `weights` is empty or every weight is zero.
+pub fn select_weighted_outgoing(weights: &[(String, u32)], rng: &mut SeededRng) -> Option<String> {
+    let total: u32 = weights.iter().map(|(_, weight)| *weight).sum();
+    if total == 0 {
+        return None;
+    }
+    let u = (rng.next_f64() * total as f64) as u32;
+    let mut running = 0u32;
+    for (flow_id, weight) in weights {
+        running += weight;
+        if u < running {
+            return Some(flow_id.clone());
+        }
+    }
+    // Floating-point rounding can leave `u` just shy of `total`; fall back
+    // to the last flow rather than drop the draw.
+    weights.last().map(|(flow_id, _)| flow_id.clone())
+}
+
+/// Pairs the gateway's *actual* outgoing flows - as derived from
+/// `sequence_flows` via `ProcessIndex`, the same source `explore` uses -
+/// with their weight, defaulting to a weight of 1 for any outgoing flow
+/// with no explicit entry in `weights`. A `Gateway`'s own `outgoing` field
+/// is never consulted here, so a stale or hand-edited entry on the
+/// gateway itself can't make a simulated run diverge from what `explore`
+/// considers reachable.
+fn weighted_outgoing(weights: &HashMap<String, u32>, outgoing_flow_ids: &[&str]) -> Vec<(String, u32)> {
+    outgoing_flow_ids
+        .iter()
+        .map(|flow_id| (flow_id.to_string(), *weights.get(*flow_id).unwrap_or(&1)))
+        .collect()
+}
+
+/// Fires `node_id` as part of a simulation run: identical to
+/// `snapshot::fire_node_outcomes` except that an exclusive split commits
+/// to a single outgoing flow, chosen by `select_weighted_outgoing`,
+/// instead of branching over every possibility.
+fn fire_node_simulated(
+    process: &Process,
+    index: &ProcessIndex,
+    snapshot: &Snapshot,
+    node_id: &str,
+    rng: &mut SeededRng,
+) -> Snapshot {
+    let mut next = consume_enabling_tokens(process, index, snapshot, node_id);
+
+    match find_gateway(process, node_id) {
+        Some(Gateway::Exclusive(g)) if g.direction == GatewayDirection::Split => {
+            let outgoing = index.outgoing_flow_ids(process, node_id);
+            if let Some(flow_id) = select_weighted_outgoing(&weighted_outgoing(&g.weights, &outgoing), rng) {
+                next.add_token(&flow_id);
+            }
+        }
+        _ => {
+            for flow_id in index.outgoing_flow_ids(process, node_id) {
+                next.add_token(flow_id);
+            }
+        }
+    }
+    next
+}
+
+/// Runs a process from its initial snapshot until no node is enabled or
+/// `max_steps` firings have happened, returning the id of each node fired
+/// in order. Exclusive splits are resolved by weighted random draw, so the
+/// same `seed` always reproduces the same trace.
+pub fn simulate_run(process: &Process, seed: u64, max_steps: usize) -> Result<Vec<String>, IndexError> {
+    let index = ProcessIndex::build(process)?;
+    let mut rng = SeededRng::new(seed);
+    let mut snapshot = initial_snapshot(process);
+    let mut trace = Vec::new();
+
+    for _ in 0..max_steps {
+        let Some(node_id) = enabled_nodes(process, &index, &snapshot).into_iter().next() else {
+            break;
+        };
+        snapshot = fire_node_simulated(process, &index, &snapshot, &node_id, &mut rng);
+        trace.push(node_id);
+    }
+
+    Ok(trace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::definitions::{EndEvent, ExclusiveGateway, SequenceFlow, StartEvent, Task};
+    use crate::types::non_empty::NonEmptyVec;
+
+    fn flow(id: &str, source_ref: &str, target_ref: &str) -> SequenceFlow {
+        SequenceFlow {
+            id: id.to_string(),
+            name: None,
+            source_ref: source_ref.to_string(),
+            target_ref: target_ref.to_string(),
+        }
+    }
+
+    /// `Start -> Split(Exclusive, weighted) -> (T1, T2) -> End`.
+    fn weighted_exclusive_split_process() -> Process {
+        let mut weights = HashMap::new();
+        weights.insert("fx1".to_string(), 9);
+        weights.insert("fx2".to_string(), 1);
+
+        Process {
+            id: "weighted".to_string(),
+            name: None,
+            start_events: NonEmptyVec::new(StartEvent {
+                id: "s".to_string(),
+                name: None,
+            }),
+            tasks: vec![
+                Task {
+                    id: "t1".to_string(),
+                    name: None,
+                },
+                Task {
+                    id: "t2".to_string(),
+                    name: None,
+                },
+            ],
+            gateways: vec![Gateway::Exclusive(ExclusiveGateway {
+                id: "split".to_string(),
+                name: None,
+                direction: GatewayDirection::Split,
+                incoming: vec!["f0".to_string()],
+                outgoing: vec!["fx1".to_string(), "fx2".to_string()],
+                weights,
+            })],
+            end_events: vec![EndEvent {
+                id: "end".to_string(),
+                name: None,
+            }],
+            sequence_flows: vec![
+                flow("f0", "s", "split"),
+                flow("fx1", "split", "t1"),
+                flow("fx2", "split", "t2"),
+                flow("fend1", "t1", "end"),
+                flow("fend2", "t2", "end"),
+            ],
+        }
+    }
+
+    #[test]
+    fn same_seed_reproduces_the_same_trace() {
+        let process = weighted_exclusive_split_process();
+        let first = simulate_run(&process, 42, 10).unwrap();
+        let second = simulate_run(&process, 42, 10).unwrap();
+        assert_eq!(first, second);
+        assert!(!first.is_empty());
+    }
+
+    #[test]
+    fn select_weighted_outgoing_is_deterministic_per_seed() {
+        let weights = vec![("a".to_string(), 9), ("b".to_string(), 1)];
+        let mut rng = SeededRng::new(7);
+        let mut rng_again = SeededRng::new(7);
+        assert_eq!(
+            select_weighted_outgoing(&weights, &mut rng),
+            select_weighted_outgoing(&weights, &mut rng_again)
+        );
+    }
+
+    #[test]
+    fn select_weighted_outgoing_returns_none_when_all_weights_are_zero() {
+        let weights = vec![("a".to_string(), 0), ("b".to_string(), 0)];
+        let mut rng = SeededRng::new(1);
+        assert_eq!(select_weighted_outgoing(&weights, &mut rng), None);
+    }
+}
@@ -0,0 +1,15 @@
+//! Token-based execution semantics ("the token game") for BPMN processes.
+//!
+//! This module plays tokens through a `Process` step by step and explores
+//! the full space of reachable snapshots, which the `soundness` module then
+//! checks for deadlocks, improper termination, and unsafeness. Execution is
+//! driven by the `core::index::ProcessIndex` adjacency indices rather than
+//! scanning every flow node on every step.
+
+pub mod gateway;
+pub mod snapshot;
+pub mod soundness;
+
+pub use gateway::{select_weighted_outgoing, simulate_run, SeededRng};
+pub use snapshot::{explore, Edge, ReachabilityGraph, Snapshot};
+pub use soundness::{check_soundness, Violation};
@@ -0,0 +1,516 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use crate::core::definitions::{Gateway, GatewayDirection, Process};
+use crate::core::index::{IndexError, ProcessIndex};
+
+/// A multiset of tokens, keyed by the id of the `SequenceFlow` (or, before
+/// the first step, the `StartEvent`) the token currently sits on.
+///
+/// Snapshots are compared and hashed structurally so the same distribution
+/// of tokens is recognised as the same state regardless of how it was
+/// reached, which is what lets `explore` terminate on cyclic processes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Snapshot {
+    tokens: BTreeMap<String, u32>,
+}
+
+impl Snapshot {
+    /// A snapshot with no tokens anywhere.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Number of tokens resting on the given place (a `SequenceFlow` id, or
+    /// a `StartEvent` id before that start event has fired).
+    pub fn tokens_on(&self, place_id: &str) -> u32 {
+        self.tokens.get(place_id).copied().unwrap_or(0)
+    }
+
+    /// Total number of tokens across all places.
+    pub fn total_tokens(&self) -> u32 {
+        self.tokens.values().sum()
+    }
+
+    /// Whether no tokens remain anywhere in the process.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Places currently carrying at least one token.
+    pub fn occupied_places(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.tokens.iter().map(|(id, count)| (id.as_str(), *count))
+    }
+
+    pub(crate) fn add_token(&mut self, place_id: &str) {
+        *self.tokens.entry(place_id.to_string()).or_insert(0) += 1;
+    }
+
+    pub(crate) fn remove_token(&mut self, place_id: &str) {
+        if let Some(count) = self.tokens.get_mut(place_id) {
+            *count -= 1;
+            if *count == 0 {
+                self.tokens.remove(place_id);
+            }
+        }
+    }
+}
+
+/// The initial snapshot of a process: one token resting on each start event.
+pub fn initial_snapshot(process: &Process) -> Snapshot {
+    let mut snapshot = Snapshot::empty();
+    for start_event in process.start_events.iter() {
+        snapshot.add_token(&start_event.id);
+    }
+    snapshot
+}
+
+/// The gateway with the given id, if any.
+pub(crate) fn find_gateway<'a>(process: &'a Process, node_id: &str) -> Option<&'a Gateway> {
+    process.gateways.iter().find(|gateway| gateway.id() == node_id)
+}
+
+/// Every node reachable in one hop from a currently-occupied place: the
+/// target of each tokened sequence flow, plus any start event still
+/// holding its own initial token. Restricting `enabled_nodes` to this
+/// frontier is what makes a step proportional to the number of active
+/// tokens rather than to the size of the whole process.
+fn frontier_nodes(process: &Process, index: &ProcessIndex, snapshot: &Snapshot) -> BTreeSet<String> {
+    let mut frontier = BTreeSet::new();
+    for (place_id, count) in snapshot.occupied_places() {
+        if count == 0 {
+            continue;
+        }
+        match index.target_of(process, place_id) {
+            Some(target) => {
+                frontier.insert(target.to_string());
+            }
+            None => {
+                // Not a sequence flow id: this is a start event resting on
+                // its own place before its first firing.
+                frontier.insert(place_id.to_string());
+            }
+        }
+    }
+    frontier
+}
+
+/// Whether `node_id` is an explicit AND-join: a `Parallel` or `Inclusive`
+/// gateway in the `Join` direction. Only these synchronise their incoming
+/// flows; every other multi-incoming node - plain tasks and end events
+/// with converging branches, and exclusive joins - is an implicit merge,
+/// firing on any single incoming token.
+fn requires_all_incoming(process: &Process, node_id: &str) -> bool {
+    matches!(
+        find_gateway(process, node_id),
+        Some(Gateway::Parallel(g)) if g.direction == GatewayDirection::Join
+    ) || matches!(
+        find_gateway(process, node_id),
+        Some(Gateway::Inclusive(g)) if g.direction == GatewayDirection::Join
+    )
+}
+
+/// Whether `node_id` is enabled to fire in `snapshot`.
+///
+/// A start event is enabled while its own place still holds its initial
+/// token. A parallel or inclusive join is enabled once every one of its
+/// incoming flows carries a token. Every other node - including exclusive
+/// joins and plain tasks/end events with multiple incoming flows - is an
+/// implicit merge, enabled once any single incoming flow carries a token.
+fn is_enabled(process: &Process, index: &ProcessIndex, snapshot: &Snapshot, node_id: &str) -> bool {
+    let incoming = index.incoming_flow_ids(process, node_id);
+    if incoming.is_empty() {
+        // No incoming flows: this is a start event, enabled while its own
+        // place (keyed by its own id) still holds a token.
+        return snapshot.tokens_on(node_id) > 0;
+    }
+    if requires_all_incoming(process, node_id) {
+        incoming.iter().all(|flow_id| snapshot.tokens_on(flow_id) > 0)
+    } else {
+        incoming.iter().any(|flow_id| snapshot.tokens_on(flow_id) > 0)
+    }
+}
+
+/// Node ids in the active frontier of `snapshot` that are enabled to fire.
+pub fn enabled_nodes(process: &Process, index: &ProcessIndex, snapshot: &Snapshot) -> Vec<String> {
+    frontier_nodes(process, index, snapshot)
+        .into_iter()
+        .filter(|node_id| is_enabled(process, index, snapshot, node_id))
+        .collect()
+}
+
+/// Consumes the tokens that enable `node_id`, without yet producing any
+/// downstream tokens. Shared by `fire_node_outcomes` and the simulator.
+pub(crate) fn consume_enabling_tokens(
+    process: &Process,
+    index: &ProcessIndex,
+    snapshot: &Snapshot,
+    node_id: &str,
+) -> Snapshot {
+    let mut consumed = snapshot.clone();
+    let incoming = index.incoming_flow_ids(process, node_id);
+    if incoming.is_empty() {
+        consumed.remove_token(node_id);
+        return consumed;
+    }
+    if requires_all_incoming(process, node_id) {
+        for flow_id in incoming {
+            consumed.remove_token(flow_id);
+        }
+    } else if let Some(flow_id) = incoming.iter().find(|flow_id| snapshot.tokens_on(flow_id) > 0) {
+        consumed.remove_token(flow_id);
+    }
+    consumed
+}
+
+/// Fires `node_id`, returning every snapshot it could produce.
+///
+/// Most nodes are deterministic and produce exactly one outcome: a token
+/// is consumed from each incoming flow (or the node's own place, for a
+/// start event) and produced on each outgoing flow. An exclusive split is
+/// the one exception - only one outgoing flow ever receives a token, so
+/// this returns one outcome per outgoing flow, branching the reachability
+/// exploration over every path the gateway could take. For a single
+/// random pick weighted by the gateway's `weights`, use
+/// `execution::gateway::simulate_run` instead.
+pub fn fire_node_outcomes(
+    process: &Process,
+    index: &ProcessIndex,
+    snapshot: &Snapshot,
+    node_id: &str,
+) -> Vec<Snapshot> {
+    fire_node_outcomes_via(process, index, snapshot, node_id)
+        .into_iter()
+        .map(|(_, outcome)| outcome)
+        .collect()
+}
+
+/// As `fire_node_outcomes`, additionally reporting which outgoing flow
+/// produced each outcome when firing the node branches.
+pub(crate) fn fire_node_outcomes_via(
+    process: &Process,
+    index: &ProcessIndex,
+    snapshot: &Snapshot,
+    node_id: &str,
+) -> Vec<(Option<String>, Snapshot)> {
+    let consumed = consume_enabling_tokens(process, index, snapshot, node_id);
+    let outgoing = index.outgoing_flow_ids(process, node_id);
+
+    match find_gateway(process, node_id) {
+        Some(Gateway::Exclusive(g)) if g.direction == GatewayDirection::Split => outgoing
+            .into_iter()
+            .map(|flow_id| {
+                let mut next = consumed.clone();
+                next.add_token(flow_id);
+                (Some(flow_id.to_string()), next)
+            })
+            .collect(),
+        _ => {
+            let mut next = consumed;
+            for flow_id in outgoing {
+                next.add_token(flow_id);
+            }
+            vec![(None, next)]
+        }
+    }
+}
+
+/// An edge in the `ReachabilityGraph`: firing `fired_node` moves the
+/// process from `from` to `to`, taking `via_flow` when firing the node
+/// branches (only possible for an exclusive split).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub fired_node: String,
+    pub via_flow: Option<String>,
+}
+
+/// The full space of snapshots reachable from the initial snapshot of a
+/// process, together with the firings that connect them.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ReachabilityGraph {
+    pub snapshots: Vec<Snapshot>,
+    pub edges: Vec<Edge>,
+}
+
+impl ReachabilityGraph {
+    /// Outgoing edges of the snapshot at `index`, i.e. every node whose
+    /// firing was explored from that snapshot.
+    pub fn edges_from(&self, index: usize) -> impl Iterator<Item = &Edge> {
+        self.edges.iter().filter(move |edge| edge.from == index)
+    }
+}
+
+/// The highest token count a place can carry while still being expanded
+/// further during `explore`. A count beyond this is already enough for
+/// `check_soundness` to report `Violation::Unsafe` on that place, so there
+/// is nothing more to learn by continuing to fire from there - and an
+/// unbounded process (a cycle that keeps stacking tokens onto some place
+/// without ever being consumed) would otherwise never reach a fixed point.
+const MAX_TOKENS_PER_PLACE: u32 = 1;
+
+/// Whether every place in `snapshot` is still within the bound `explore`
+/// expands past. A snapshot that exceeds it is still recorded in the
+/// reachability graph - so soundness checking sees it - but is treated as
+/// a dead end rather than a new frontier to fire from.
+fn within_exploration_bound(snapshot: &Snapshot) -> bool {
+    snapshot.occupied_places().all(|(_, count)| count <= MAX_TOKENS_PER_PLACE)
+}
+
+/// Breadth-first exploration of every snapshot reachable from the initial
+/// snapshot (one token on each start event), recording the transitions
+/// between them as a reachability graph.
+///
+/// Builds a `ProcessIndex` up front, so dangling `source_ref`/`target_ref`
+/// values are rejected before exploration rather than silently ignored,
+/// and each step only visits the frontier of nodes reachable from
+/// currently-tokened places instead of every flow node in the process.
+///
+/// A process whose token count on some place is unbounded (e.g. a gateway
+/// whose own outgoing flow loops back into one of its incoming flows)
+/// would otherwise make this BFS run forever, growing memory without
+/// limit. Once a reachable snapshot exceeds `MAX_TOKENS_PER_PLACE` on any
+/// place it is kept in the graph - `check_soundness` still flags it as
+/// `Violation::Unsafe` - but is not expanded further, which keeps
+/// exploration finite without losing that violation.
+pub fn explore(process: &Process) -> Result<ReachabilityGraph, IndexError> {
+    let index = ProcessIndex::build(process)?;
+    let mut graph = ReachabilityGraph::default();
+    let mut visited: HashMap<Snapshot, usize> = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    let initial = initial_snapshot(process);
+    visited.insert(initial.clone(), 0);
+    graph.snapshots.push(initial.clone());
+    queue.push_back((0usize, initial));
+
+    while let Some((from_index, snapshot)) = queue.pop_front() {
+        for node_id in enabled_nodes(process, &index, &snapshot) {
+            for (via_flow, next) in fire_node_outcomes_via(process, &index, &snapshot, &node_id) {
+                let to_index = match visited.get(&next) {
+                    Some(&i) => i,
+                    None => {
+                        let i = graph.snapshots.len();
+                        visited.insert(next.clone(), i);
+                        graph.snapshots.push(next.clone());
+                        if within_exploration_bound(&next) {
+                            queue.push_back((i, next));
+                        }
+                        i
+                    }
+                };
+                graph.edges.push(Edge {
+                    from: from_index,
+                    to: to_index,
+                    fired_node: node_id.clone(),
+                    via_flow,
+                });
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::core::definitions::{EndEvent, ExclusiveGateway, ParallelGateway, SequenceFlow, StartEvent, Task};
+    use crate::types::non_empty::NonEmptyVec;
+
+    fn flow(id: &str, source_ref: &str, target_ref: &str) -> SequenceFlow {
+        SequenceFlow {
+            id: id.to_string(),
+            name: None,
+            source_ref: source_ref.to_string(),
+            target_ref: target_ref.to_string(),
+        }
+    }
+
+    fn task(id: &str) -> Task {
+        Task {
+            id: id.to_string(),
+            name: None,
+        }
+    }
+
+    /// `Start -> T1 -> End`: the simplest possible sound process.
+    fn linear_process() -> Process {
+        Process {
+            id: "linear".to_string(),
+            name: None,
+            start_events: NonEmptyVec::new(StartEvent {
+                id: "s".to_string(),
+                name: None,
+            }),
+            tasks: vec![task("t1")],
+            gateways: vec![],
+            end_events: vec![EndEvent {
+                id: "end".to_string(),
+                name: None,
+            }],
+            sequence_flows: vec![flow("f0", "s", "t1"), flow("f1", "t1", "end")],
+        }
+    }
+
+    /// `Start -> Split(Parallel) -> (Ta, Tb) -> Join(Parallel) -> End`: both
+    /// branches must complete before the join fires.
+    fn parallel_split_join_process() -> Process {
+        Process {
+            id: "parallel".to_string(),
+            name: None,
+            start_events: NonEmptyVec::new(StartEvent {
+                id: "s".to_string(),
+                name: None,
+            }),
+            tasks: vec![task("ta"), task("tb")],
+            gateways: vec![
+                Gateway::Parallel(ParallelGateway {
+                    id: "split".to_string(),
+                    name: None,
+                    direction: GatewayDirection::Split,
+                    incoming: vec!["f0".to_string()],
+                    outgoing: vec!["fa".to_string(), "fb".to_string()],
+                }),
+                Gateway::Parallel(ParallelGateway {
+                    id: "join".to_string(),
+                    name: None,
+                    direction: GatewayDirection::Join,
+                    incoming: vec!["fja".to_string(), "fjb".to_string()],
+                    outgoing: vec!["fend".to_string()],
+                }),
+            ],
+            end_events: vec![EndEvent {
+                id: "end".to_string(),
+                name: None,
+            }],
+            sequence_flows: vec![
+                flow("f0", "s", "split"),
+                flow("fa", "split", "ta"),
+                flow("fb", "split", "tb"),
+                flow("fja", "ta", "join"),
+                flow("fjb", "tb", "join"),
+                flow("fend", "join", "end"),
+            ],
+        }
+    }
+
+    /// `Start -> Split(Exclusive) -> (T1, T2)`, both of which flow directly
+    /// into `Tend` with no join gateway - the implicit-merge regression
+    /// case from the review: `Tend` must fire on either branch alone.
+    fn exclusive_split_implicit_merge_process() -> Process {
+        Process {
+            id: "exclusive-merge".to_string(),
+            name: None,
+            start_events: NonEmptyVec::new(StartEvent {
+                id: "s".to_string(),
+                name: None,
+            }),
+            tasks: vec![task("t1"), task("t2"), task("tend")],
+            gateways: vec![Gateway::Exclusive(ExclusiveGateway {
+                id: "split".to_string(),
+                name: None,
+                direction: GatewayDirection::Split,
+                incoming: vec!["f0".to_string()],
+                outgoing: vec!["fx1".to_string(), "fx2".to_string()],
+                weights: HashMap::new(),
+            })],
+            end_events: vec![EndEvent {
+                id: "end".to_string(),
+                name: None,
+            }],
+            sequence_flows: vec![
+                flow("f0", "s", "split"),
+                flow("fx1", "split", "t1"),
+                flow("fx2", "split", "t2"),
+                flow("fend1", "t1", "tend"),
+                flow("fend2", "t2", "tend"),
+                flow("fout", "tend", "end"),
+            ],
+        }
+    }
+
+    #[test]
+    fn linear_process_runs_to_completion() {
+        let process = linear_process();
+        let graph = explore(&process).unwrap();
+        let empty = Snapshot::empty();
+        assert!(graph.snapshots.contains(&empty));
+    }
+
+    #[test]
+    fn parallel_join_waits_for_both_branches() {
+        let process = parallel_split_join_process();
+        let graph = explore(&process).unwrap();
+        let empty = Snapshot::empty();
+        assert!(graph.snapshots.contains(&empty));
+
+        // The join must never fire having consumed only one branch: no
+        // reachable snapshot has a token resting on `fend` while either
+        // branch's join-incoming flow is still tokened.
+        for snapshot in &graph.snapshots {
+            if snapshot.tokens_on("fend") > 0 {
+                assert_eq!(snapshot.tokens_on("fja"), 0);
+                assert_eq!(snapshot.tokens_on("fjb"), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn exclusive_split_merges_implicitly_without_a_join_gateway() {
+        // Regression test: a plain task with two converging branches is an
+        // implicit merge and must fire on either branch alone, not deadlock
+        // waiting for both.
+        let process = exclusive_split_implicit_merge_process();
+        let graph = explore(&process).unwrap();
+        let empty = Snapshot::empty();
+        assert!(graph.snapshots.contains(&empty));
+    }
+
+    /// `Start -> Split(Parallel)`, where `split`'s own outgoing flow
+    /// (`floop`) loops back into one of its incoming flows: every time
+    /// `floop` re-enables the split, it fans out onto `fa` and `floop`
+    /// again, stacking an extra token onto `fa` (unless `end` consumes it
+    /// first) each round while `floop` resets to one.
+    fn unbounded_self_looping_split_process() -> Process {
+        Process {
+            id: "unbounded".to_string(),
+            name: None,
+            start_events: NonEmptyVec::new(StartEvent {
+                id: "s".to_string(),
+                name: None,
+            }),
+            tasks: vec![],
+            gateways: vec![Gateway::Parallel(ParallelGateway {
+                id: "split".to_string(),
+                name: None,
+                direction: GatewayDirection::Split,
+                incoming: vec!["f0".to_string(), "floop".to_string()],
+                outgoing: vec!["fa".to_string(), "floop".to_string()],
+            })],
+            end_events: vec![EndEvent {
+                id: "end".to_string(),
+                name: None,
+            }],
+            sequence_flows: vec![flow("f0", "s", "split"), flow("floop", "split", "split"), flow("fa", "split", "end")],
+        }
+    }
+
+    #[test]
+    fn explore_terminates_on_a_process_with_an_unbounded_place() {
+        // Regression test: without a bound on exploration, this process's
+        // `fa` place grows by one token every time `split` re-fires via its
+        // self-loop, and `explore` would never reach a fixed point.
+        let process = unbounded_self_looping_split_process();
+        let graph = explore(&process).unwrap();
+
+        // The reachable state space is finite only because exploration
+        // stops expanding a snapshot once some place exceeds the safe
+        // token bound - confirm that cutoff snapshot is still present so
+        // `check_soundness` can flag it.
+        assert!(graph.snapshots.len() < 20);
+        assert!(graph.snapshots.iter().any(|snapshot| snapshot.tokens_on("fa") > 1));
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+
+use crate::core::definitions::Process;
+use crate::core::index::{IndexError, ProcessIndex};
+use crate::execution::snapshot::{enabled_nodes, ReachabilityGraph, Snapshot};
+
+/// A soundness property violated somewhere in a process's reachability
+/// graph, together with the snapshot in which it was observed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// A reachable snapshot still holds tokens but no node is enabled to
+    /// fire, so the process can never reach completion from here.
+    Deadlock { snapshot: Snapshot },
+    /// An end event fired while tokens were still present elsewhere in the
+    /// process, so the process reported completion without every branch
+    /// having finished.
+    ImproperTermination { snapshot: Snapshot },
+    /// More than one token ever accumulated on a single sequence flow.
+    Unsafe { flow_id: String, snapshot: Snapshot },
+}
+
+/// Checks a process's reachability graph for deadlocks, improper
+/// termination, and unsafeness, returning every offending snapshot found.
+///
+/// `graph` is expected to have come from `explore(process)`, so rebuilding
+/// the `ProcessIndex` here is expected to succeed; it is only fallible at
+/// all because it re-validates `process` rather than trusting the caller.
+pub fn check_soundness(process: &Process, graph: &ReachabilityGraph) -> Result<Vec<Violation>, IndexError> {
+    let index = ProcessIndex::build(process)?;
+    let mut violations = Vec::new();
+
+    let end_event_ids: HashSet<&str> = process
+        .end_events
+        .iter()
+        .map(|end_event| end_event.id.as_str())
+        .collect();
+
+    for snapshot in &graph.snapshots {
+        if !snapshot.is_empty() && enabled_nodes(process, &index, snapshot).is_empty() {
+            violations.push(Violation::Deadlock {
+                snapshot: snapshot.clone(),
+            });
+        }
+
+        for (place_id, count) in snapshot.occupied_places() {
+            if count > 1 {
+                violations.push(Violation::Unsafe {
+                    flow_id: place_id.to_string(),
+                    snapshot: snapshot.clone(),
+                });
+            }
+        }
+    }
+
+    for edge in &graph.edges {
+        if end_event_ids.contains(edge.fired_node.as_str()) {
+            let resulting = &graph.snapshots[edge.to];
+            if resulting.total_tokens() > 0 {
+                violations.push(Violation::ImproperTermination {
+                    snapshot: resulting.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::definitions::{
+        EndEvent, Gateway, GatewayDirection, ParallelGateway, Process, SequenceFlow, StartEvent, Task,
+    };
+    use crate::execution::snapshot::explore;
+    use crate::types::non_empty::NonEmptyVec;
+
+    use super::*;
+
+    fn flow(id: &str, source_ref: &str, target_ref: &str) -> SequenceFlow {
+        SequenceFlow {
+            id: id.to_string(),
+            name: None,
+            source_ref: source_ref.to_string(),
+            target_ref: target_ref.to_string(),
+        }
+    }
+
+    fn task(id: &str) -> Task {
+        Task {
+            id: id.to_string(),
+            name: None,
+        }
+    }
+
+    fn parallel_gateway(id: &str, direction: GatewayDirection, incoming: &[&str], outgoing: &[&str]) -> Gateway {
+        Gateway::Parallel(ParallelGateway {
+            id: id.to_string(),
+            name: None,
+            direction,
+            incoming: incoming.iter().map(|s| s.to_string()).collect(),
+            outgoing: outgoing.iter().map(|s| s.to_string()).collect(),
+        })
+    }
+
+    /// An AND-join with an incoming flow from `orphan`, a task with no
+    /// incoming flow of its own: `orphan` can never receive a token, so
+    /// that incoming flow never fires and the join can never synchronise,
+    /// deadlocking once the other two branches have completed.
+    fn genuine_deadlock_process() -> Process {
+        Process {
+            id: "deadlock".to_string(),
+            name: None,
+            start_events: NonEmptyVec::new(StartEvent {
+                id: "s".to_string(),
+                name: None,
+            }),
+            tasks: vec![task("t1"), task("t2"), task("orphan")],
+            gateways: vec![
+                parallel_gateway("split", GatewayDirection::Split, &["f0"], &["f1", "f2"]),
+                parallel_gateway("join", GatewayDirection::Join, &["f3", "f4", "f5"], &["f6"]),
+            ],
+            end_events: vec![EndEvent {
+                id: "end".to_string(),
+                name: None,
+            }],
+            sequence_flows: vec![
+                flow("f0", "s", "split"),
+                flow("f1", "split", "t1"),
+                flow("f2", "split", "t2"),
+                flow("f3", "t1", "join"),
+                flow("f4", "t2", "join"),
+                flow("f5", "orphan", "join"),
+                flow("f6", "join", "end"),
+            ],
+        }
+    }
+
+    /// A parallel split feeds both of its branches directly into `m`, a
+    /// plain task with a single outgoing flow `fm` and no join gateway to
+    /// synchronise them. `m` is an implicit merge, so each branch fires it
+    /// separately; since every firing adds a token to every outgoing flow,
+    /// `m` stacks two tokens onto `fm` before anything downstream consumes
+    /// either - a genuine unsafe flow.
+    fn genuine_unsafe_process() -> Process {
+        Process {
+            id: "unsafe".to_string(),
+            name: None,
+            start_events: NonEmptyVec::new(StartEvent {
+                id: "s".to_string(),
+                name: None,
+            }),
+            tasks: vec![task("m")],
+            gateways: vec![parallel_gateway("split", GatewayDirection::Split, &["f0"], &["fa", "fb"])],
+            end_events: vec![EndEvent {
+                id: "end".to_string(),
+                name: None,
+            }],
+            sequence_flows: vec![
+                flow("f0", "s", "split"),
+                flow("fa", "split", "m"),
+                flow("fb", "split", "m"),
+                flow("fm", "m", "end"),
+            ],
+        }
+    }
+
+    /// `Start -> Split(Parallel) -> (Ta, Tb) -> Join(Parallel) -> End`: a
+    /// straightforwardly sound process, used to check that soundness
+    /// checking reports no false positives.
+    fn sound_parallel_process() -> Process {
+        Process {
+            id: "sound".to_string(),
+            name: None,
+            start_events: NonEmptyVec::new(StartEvent {
+                id: "s".to_string(),
+                name: None,
+            }),
+            tasks: vec![task("ta"), task("tb")],
+            gateways: vec![
+                parallel_gateway("split", GatewayDirection::Split, &["f0"], &["fa", "fb"]),
+                parallel_gateway("join", GatewayDirection::Join, &["fja", "fjb"], &["fend"]),
+            ],
+            end_events: vec![EndEvent {
+                id: "end".to_string(),
+                name: None,
+            }],
+            sequence_flows: vec![
+                flow("f0", "s", "split"),
+                flow("fa", "split", "ta"),
+                flow("fb", "split", "tb"),
+                flow("fja", "ta", "join"),
+                flow("fjb", "tb", "join"),
+                flow("fend", "join", "end"),
+            ],
+        }
+    }
+
+    #[test]
+    fn sound_process_has_no_violations() {
+        let process = sound_parallel_process();
+        let graph = explore(&process).unwrap();
+        let violations = check_soundness(&process, &graph).unwrap();
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn detects_genuine_deadlock() {
+        let process = genuine_deadlock_process();
+        let graph = explore(&process).unwrap();
+        let violations = check_soundness(&process, &graph).unwrap();
+        assert!(violations.iter().any(|v| matches!(v, Violation::Deadlock { .. })));
+    }
+
+    #[test]
+    fn detects_genuine_unsafe_flow() {
+        let process = genuine_unsafe_process();
+        let graph = explore(&process).unwrap();
+        let violations = check_soundness(&process, &graph).unwrap();
+        assert!(violations
+            .iter()
+            .any(|v| matches!(v, Violation::Unsafe { flow_id, .. } if flow_id == "fm")));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod core;
+pub mod execution;
+pub mod types;
+pub mod xml;
@@ -0,0 +1 @@
+pub mod non_empty;
@@ -32,6 +32,22 @@ impl<T> NonEmptyVec<T> {
         1 + self.tail.len()
     }
 
+    /// Always `false`: a `NonEmptyVec` can never be empty by construction.
+    /// Exists so clippy's `len_without_is_empty` doesn't flag `len`.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Build a `NonEmptyVec` from a plain `Vec`, returning `None` if it was
+    /// empty.
+    pub fn from_vec(mut items: Vec<T>) -> Option<Self> {
+        if items.is_empty() {
+            return None;
+        }
+        let head = items.remove(0);
+        Some(Self { head, tail: items })
+    }
+
     /// Iterator over all elements
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         std::iter::once(&self.head).chain(self.tail.iter())
@@ -0,0 +1,94 @@
+use crate::core::definitions::{Definitions, Gateway, Process};
+use crate::xml::reader::escape;
+
+const BPMN_NAMESPACE: &str = "http://www.omg.org/spec/BPMN/20100524/MODEL";
+
+/// Serializes this crate's model back out as a BPMN 2.0 `<definitions>`
+/// document, preserving `targetNamespace` and every element's `id`/`name`
+/// attribute.
+pub fn export_definitions(definitions: &Definitions) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<definitions xmlns=\"");
+    out.push_str(BPMN_NAMESPACE);
+    out.push('"');
+    write_optional_attr(&mut out, "name", definitions.name.as_deref());
+    write_optional_attr(&mut out, "targetNamespace", definitions.target_namespace.as_deref());
+    out.push_str(">\n");
+
+    for process in &definitions.processes {
+        write_process(&mut out, process);
+    }
+
+    out.push_str("</definitions>\n");
+    out
+}
+
+fn write_process(out: &mut String, process: &Process) {
+    out.push_str("  <process id=\"");
+    out.push_str(&escape(&process.id));
+    out.push('"');
+    write_optional_attr(out, "name", process.name.as_deref());
+    out.push_str(">\n");
+
+    for start_event in process.start_events.iter() {
+        write_leaf(out, "startEvent", &start_event.id, start_event.name.as_deref(), &[]);
+    }
+    for task in &process.tasks {
+        write_leaf(out, "task", &task.id, task.name.as_deref(), &[]);
+    }
+    for gateway in &process.gateways {
+        let tag = match gateway {
+            Gateway::Exclusive(_) => "exclusiveGateway",
+            Gateway::Parallel(_) => "parallelGateway",
+            Gateway::Inclusive(_) => "inclusiveGateway",
+        };
+        let name = match gateway {
+            Gateway::Exclusive(g) => g.name.as_deref(),
+            Gateway::Parallel(g) => g.name.as_deref(),
+            Gateway::Inclusive(g) => g.name.as_deref(),
+        };
+        write_leaf(out, tag, gateway.id(), name, &[]);
+    }
+    for end_event in &process.end_events {
+        write_leaf(out, "endEvent", &end_event.id, end_event.name.as_deref(), &[]);
+    }
+    for flow in &process.sequence_flows {
+        write_leaf(
+            out,
+            "sequenceFlow",
+            &flow.id,
+            flow.name.as_deref(),
+            &[("sourceRef", flow.source_ref.as_str()), ("targetRef", flow.target_ref.as_str())],
+        );
+    }
+
+    out.push_str("  </process>\n");
+}
+
+fn write_leaf(out: &mut String, tag: &str, id: &str, name: Option<&str>, extra_attrs: &[(&str, &str)]) {
+    out.push_str("    <");
+    out.push_str(tag);
+    out.push_str(" id=\"");
+    out.push_str(&escape(id));
+    out.push('"');
+    for (attr, value) in extra_attrs {
+        out.push(' ');
+        out.push_str(attr);
+        out.push_str("=\"");
+        out.push_str(&escape(value));
+        out.push('"');
+    }
+    write_optional_attr(out, "name", name);
+    out.push_str("/>\n");
+}
+
+fn write_optional_attr(out: &mut String, attr: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        out.push(' ');
+        out.push_str(attr);
+        out.push_str("=\"");
+        out.push_str(&escape(value));
+        out.push('"');
+    }
+}
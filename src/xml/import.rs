@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
+use crate::core::definitions::{
+    Definitions, EndEvent, ExclusiveGateway, Gateway, GatewayDirection, InclusiveGateway, ParallelGateway, Process,
+    SequenceFlow, StartEvent, Task,
+};
+use crate::types::non_empty::NonEmptyVec;
+use crate::xml::reader::{local_name, tokenize, Token, XmlError};
+
+/// A BPMN XML document could not be imported into this crate's model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError {
+    Xml(XmlError),
+    /// A `<process>` had no `<startEvent>`, so the `NonEmptyVec<StartEvent>`
+    /// invariant on `Process` could not be satisfied.
+    NoStartEvents { process_id: String },
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Xml(e) => write!(f, "{e}"),
+            ImportError::NoStartEvents { process_id } => {
+                write!(f, "process \"{process_id}\" has no start events")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<XmlError> for ImportError {
+    fn from(error: XmlError) -> Self {
+        ImportError::Xml(error)
+    }
+}
+
+/// Parses a BPMN 2.0 `<definitions>` document into this crate's model.
+pub fn import_definitions(xml: &str) -> Result<Definitions, ImportError> {
+    let mut tokens = tokenize(xml)?.into_iter().peekable();
+
+    let definitions_attrs = loop {
+        match tokens.next() {
+            Some(Token::Start { name, attrs }) if local_name(&name) == "definitions" => break attrs,
+            Some(Token::Empty { name, attrs }) if local_name(&name) == "definitions" => {
+                return Ok(Definitions {
+                    name: attrs.get("name").cloned(),
+                    target_namespace: attrs.get("targetNamespace").cloned(),
+                    processes: Vec::new(),
+                });
+            }
+            Some(_) => continue,
+            None => return Err(XmlError("no <definitions> element found".to_string()).into()),
+        }
+    };
+
+    let mut processes = Vec::new();
+    loop {
+        match tokens.next() {
+            Some(Token::Start { name, attrs }) if local_name(&name) == "process" => {
+                processes.push(import_process(attrs, &mut tokens)?);
+            }
+            Some(Token::End { name }) if local_name(&name) == "definitions" => break,
+            Some(_) => continue,
+            None => return Err(XmlError("unterminated <definitions>".to_string()).into()),
+        }
+    }
+
+    Ok(Definitions {
+        name: definitions_attrs.get("name").cloned(),
+        target_namespace: definitions_attrs.get("targetNamespace").cloned(),
+        processes,
+    })
+}
+
+/// Consumes tokens up to and including the `</tag_name>` that closes the
+/// element whose opening tag was just read, ignoring everything in
+/// between: child elements this crate does not model (documentation,
+/// extension elements, conditions, ...).
+fn skip_to_matching_end(tokens: &mut Peekable<IntoIter<Token>>, tag_name: &str) {
+    let mut depth = 1;
+    while depth > 0 {
+        match tokens.next() {
+            Some(Token::Start { name, .. }) if local_name(&name) == tag_name => depth += 1,
+            Some(Token::End { name }) if local_name(&name) == tag_name => depth -= 1,
+            Some(_) => {}
+            None => break,
+        }
+    }
+}
+
+struct GatewayStub {
+    id: String,
+    name: Option<String>,
+}
+
+fn import_process(attrs: HashMap<String, String>, tokens: &mut Peekable<IntoIter<Token>>) -> Result<Process, ImportError> {
+    let process_id = attrs.get("id").cloned().unwrap_or_default();
+    let process_name = attrs.get("name").cloned();
+
+    let mut start_events = Vec::new();
+    let mut tasks = Vec::new();
+    let mut end_events = Vec::new();
+    let mut sequence_flows = Vec::new();
+    let mut exclusive_stubs = Vec::new();
+    let mut parallel_stubs = Vec::new();
+    let mut inclusive_stubs = Vec::new();
+
+    loop {
+        let token = tokens
+            .next()
+            .ok_or_else(|| XmlError(format!("unterminated <process id=\"{process_id}\">")))?;
+
+        let (local, elem_attrs, was_container) = match token {
+            Token::End { name } if local_name(&name) == "process" => break,
+            Token::Start { name, attrs } => (local_name(&name).to_string(), attrs, true),
+            Token::Empty { name, attrs } => (local_name(&name).to_string(), attrs, false),
+            Token::End { .. } => continue,
+        };
+
+        match local.as_str() {
+            "startEvent" => start_events.push(StartEvent {
+                id: elem_attrs.get("id").cloned().unwrap_or_default(),
+                name: elem_attrs.get("name").cloned(),
+            }),
+            "endEvent" => end_events.push(EndEvent {
+                id: elem_attrs.get("id").cloned().unwrap_or_default(),
+                name: elem_attrs.get("name").cloned(),
+            }),
+            "sequenceFlow" => sequence_flows.push(SequenceFlow {
+                id: elem_attrs.get("id").cloned().unwrap_or_default(),
+                name: elem_attrs.get("name").cloned(),
+                source_ref: elem_attrs.get("sourceRef").cloned().unwrap_or_default(),
+                target_ref: elem_attrs.get("targetRef").cloned().unwrap_or_default(),
+            }),
+            "exclusiveGateway" => exclusive_stubs.push(GatewayStub {
+                id: elem_attrs.get("id").cloned().unwrap_or_default(),
+                name: elem_attrs.get("name").cloned(),
+            }),
+            "parallelGateway" => parallel_stubs.push(GatewayStub {
+                id: elem_attrs.get("id").cloned().unwrap_or_default(),
+                name: elem_attrs.get("name").cloned(),
+            }),
+            "inclusiveGateway" => inclusive_stubs.push(GatewayStub {
+                id: elem_attrs.get("id").cloned().unwrap_or_default(),
+                name: elem_attrs.get("name").cloned(),
+            }),
+            // `task` and its subtypes (userTask, serviceTask, scriptTask, ...)
+            // all map onto the same `Task`.
+            _ if local.ends_with("Task") || local == "task" => tasks.push(Task {
+                id: elem_attrs.get("id").cloned().unwrap_or_default(),
+                name: elem_attrs.get("name").cloned(),
+            }),
+            _ => {}
+        }
+
+        if was_container {
+            skip_to_matching_end(tokens, &local);
+        }
+    }
+
+    let gateways = exclusive_stubs
+        .into_iter()
+        .map(|stub| Gateway::Exclusive(build_exclusive(stub, &sequence_flows)))
+        .chain(parallel_stubs.into_iter().map(|stub| Gateway::Parallel(build_parallel(stub, &sequence_flows))))
+        .chain(inclusive_stubs.into_iter().map(|stub| Gateway::Inclusive(build_inclusive(stub, &sequence_flows))))
+        .collect();
+
+    let start_events = NonEmptyVec::from_vec(start_events).ok_or(ImportError::NoStartEvents {
+        process_id: process_id.clone(),
+    })?;
+
+    Ok(Process {
+        id: process_id,
+        name: process_name,
+        start_events,
+        tasks,
+        gateways,
+        end_events,
+        sequence_flows,
+    })
+}
+
+fn incoming_of(flows: &[SequenceFlow], node_id: &str) -> Vec<String> {
+    flows
+        .iter()
+        .filter(|flow| flow.target_ref == node_id)
+        .map(|flow| flow.id.clone())
+        .collect()
+}
+
+fn outgoing_of(flows: &[SequenceFlow], node_id: &str) -> Vec<String> {
+    flows
+        .iter()
+        .filter(|flow| flow.source_ref == node_id)
+        .map(|flow| flow.id.clone())
+        .collect()
+}
+
+/// A gateway's incoming/outgoing flows imply whether it splits or joins:
+/// more outgoing than incoming means it fans tokens out, otherwise it is
+/// gathering them.
+fn direction_of(incoming: &[String], outgoing: &[String]) -> GatewayDirection {
+    if outgoing.len() > incoming.len() {
+        GatewayDirection::Split
+    } else {
+        GatewayDirection::Join
+    }
+}
+
+fn build_exclusive(stub: GatewayStub, flows: &[SequenceFlow]) -> ExclusiveGateway {
+    let incoming = incoming_of(flows, &stub.id);
+    let outgoing = outgoing_of(flows, &stub.id);
+    let direction = direction_of(&incoming, &outgoing);
+    ExclusiveGateway {
+        id: stub.id,
+        name: stub.name,
+        direction,
+        incoming,
+        outgoing,
+        weights: HashMap::new(),
+    }
+}
+
+fn build_parallel(stub: GatewayStub, flows: &[SequenceFlow]) -> ParallelGateway {
+    let incoming = incoming_of(flows, &stub.id);
+    let outgoing = outgoing_of(flows, &stub.id);
+    let direction = direction_of(&incoming, &outgoing);
+    ParallelGateway {
+        id: stub.id,
+        name: stub.name,
+        direction,
+        incoming,
+        outgoing,
+    }
+}
+
+fn build_inclusive(stub: GatewayStub, flows: &[SequenceFlow]) -> InclusiveGateway {
+    let incoming = incoming_of(flows, &stub.id);
+    let outgoing = outgoing_of(flows, &stub.id);
+    let direction = direction_of(&incoming, &outgoing);
+    InclusiveGateway {
+        id: stub.id,
+        name: stub.name,
+        direction,
+        incoming,
+        outgoing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::export::export_definitions;
+
+    /// `Start -> Split(Exclusive) -> (T1, T2) -> Join(Parallel) -> End`,
+    /// covering a named process, exclusive and parallel gateways, and
+    /// sequence flows with names (inclusive gateways are not included).
+    fn sample_definitions() -> Definitions {
+        Definitions {
+            name: Some("Sample".to_string()),
+            target_namespace: Some("http://example.com/bpmn".to_string()),
+            processes: vec![Process {
+                id: "proc1".to_string(),
+                name: Some("Sample Process".to_string()),
+                start_events: NonEmptyVec::new(StartEvent {
+                    id: "s".to_string(),
+                    name: Some("Start".to_string()),
+                }),
+                tasks: vec![
+                    Task {
+                        id: "t1".to_string(),
+                        name: None,
+                    },
+                    Task {
+                        id: "t2".to_string(),
+                        name: None,
+                    },
+                ],
+                gateways: vec![
+                    Gateway::Exclusive(ExclusiveGateway {
+                        id: "split".to_string(),
+                        name: None,
+                        direction: GatewayDirection::Split,
+                        incoming: vec!["f0".to_string()],
+                        outgoing: vec!["fx1".to_string(), "fx2".to_string()],
+                        weights: HashMap::new(),
+                    }),
+                    Gateway::Parallel(ParallelGateway {
+                        id: "join".to_string(),
+                        name: None,
+                        direction: GatewayDirection::Join,
+                        incoming: vec!["fj1".to_string(), "fj2".to_string()],
+                        outgoing: vec!["fend".to_string()],
+                    }),
+                ],
+                end_events: vec![EndEvent {
+                    id: "end".to_string(),
+                    name: None,
+                }],
+                sequence_flows: vec![
+                    SequenceFlow {
+                        id: "f0".to_string(),
+                        name: Some("to split".to_string()),
+                        source_ref: "s".to_string(),
+                        target_ref: "split".to_string(),
+                    },
+                    SequenceFlow {
+                        id: "fx1".to_string(),
+                        name: None,
+                        source_ref: "split".to_string(),
+                        target_ref: "t1".to_string(),
+                    },
+                    SequenceFlow {
+                        id: "fx2".to_string(),
+                        name: None,
+                        source_ref: "split".to_string(),
+                        target_ref: "t2".to_string(),
+                    },
+                    SequenceFlow {
+                        id: "fj1".to_string(),
+                        name: None,
+                        source_ref: "t1".to_string(),
+                        target_ref: "join".to_string(),
+                    },
+                    SequenceFlow {
+                        id: "fj2".to_string(),
+                        name: None,
+                        source_ref: "t2".to_string(),
+                        target_ref: "join".to_string(),
+                    },
+                    SequenceFlow {
+                        id: "fend".to_string(),
+                        name: None,
+                        source_ref: "join".to_string(),
+                        target_ref: "end".to_string(),
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn exports_and_reimports_to_an_equivalent_definitions() {
+        let original = sample_definitions();
+        let xml = export_definitions(&original);
+        let reimported = import_definitions(&xml).unwrap();
+        assert_eq!(original, reimported);
+    }
+
+    #[test]
+    fn rejects_a_process_with_no_start_events() {
+        let xml = r#"<?xml version="1.0"?>
+<definitions xmlns="http://www.omg.org/spec/BPMN/20100524/MODEL">
+  <process id="empty">
+    <endEvent id="end"/>
+  </process>
+</definitions>"#;
+        let err = import_definitions(xml).unwrap_err();
+        assert_eq!(
+            err,
+            ImportError::NoStartEvents {
+                process_id: "empty".to_string()
+            }
+        );
+    }
+}
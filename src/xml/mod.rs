@@ -0,0 +1,12 @@
+//! Import and export of BPMN 2.0 XML documents (the
+//! `http://www.omg.org/spec/BPMN/20100524/MODEL` namespace), so this crate
+//! can interoperate with real BPMN modelers instead of only its own JSON
+//! (serde) format.
+
+pub(crate) mod reader;
+
+pub mod export;
+pub mod import;
+
+pub use export::export_definitions;
+pub use import::{import_definitions, ImportError};
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// A minimal token from a BPMN XML document. This is not a general-purpose
+/// XML parser - it only understands the subset of XML that this crate's
+/// elements are written in: tags with quoted attributes, no CDATA, no
+/// processing instructions beyond `<?xml ... ?>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Token {
+    /// `<tag attr="val">`
+    Start { name: String, attrs: HashMap<String, String> },
+    /// `</tag>`
+    End { name: String },
+    /// `<tag attr="val"/>`
+    Empty { name: String, attrs: HashMap<String, String> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XmlError(pub(crate) String);
+
+impl fmt::Display for XmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid BPMN XML: {}", self.0)
+    }
+}
+
+impl std::error::Error for XmlError {}
+
+/// Splits `xml` into a flat stream of start/end/empty tags, skipping the
+/// XML declaration, comments, and doctypes, and ignoring text content
+/// (none of the elements this crate understands carry meaningful text).
+pub(crate) fn tokenize(xml: &str) -> Result<Vec<Token>, XmlError> {
+    let mut tokens = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        let end = rest.find('>').ok_or_else(|| XmlError("unterminated tag".to_string()))?;
+        let tag = &rest[1..end];
+
+        if tag.starts_with('?') || tag.starts_with('!') {
+            // XML declaration, comment, or doctype: not modelled, skip.
+        } else if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::End { name: name.trim().to_string() });
+        } else if let Some(body) = tag.strip_suffix('/') {
+            let (name, attrs) = parse_tag_body(body)?;
+            tokens.push(Token::Empty { name, attrs });
+        } else {
+            let (name, attrs) = parse_tag_body(tag)?;
+            tokens.push(Token::Start { name, attrs });
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    Ok(tokens)
+}
+
+fn parse_tag_body(body: &str) -> Result<(String, HashMap<String, String>), XmlError> {
+    let body = body.trim();
+    let name_end = body.find(char::is_whitespace).unwrap_or(body.len());
+    let name = body[..name_end].to_string();
+
+    let mut attrs = HashMap::new();
+    let mut rest = body[name_end..].trim_start();
+    while !rest.is_empty() {
+        let eq = rest
+            .find('=')
+            .ok_or_else(|| XmlError(format!("malformed attribute in <{name}>")))?;
+        let attr_name = rest[..eq].trim().to_string();
+        rest = rest[eq + 1..].trim_start();
+
+        let quote = rest
+            .chars()
+            .next()
+            .ok_or_else(|| XmlError(format!("malformed attribute in <{name}>")))?;
+        if quote != '"' && quote != '\'' {
+            return Err(XmlError(format!("malformed attribute in <{name}>")));
+        }
+        rest = &rest[1..];
+
+        let close = rest
+            .find(quote)
+            .ok_or_else(|| XmlError(format!("unterminated attribute value in <{name}>")))?;
+        attrs.insert(attr_name, unescape(&rest[..close]));
+        rest = rest[close + 1..].trim_start();
+    }
+
+    Ok((name, attrs))
+}
+
+/// The local part of a (possibly namespace-prefixed) element name, e.g.
+/// `"process"` for both `"process"` and `"bpmn:process"`.
+pub(crate) fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Escapes the characters that are not allowed verbatim inside an XML
+/// attribute value.
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}